@@ -0,0 +1,81 @@
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+
+use lol::parser::{parse, partially_evaluate, tokenize, Env, Eval};
+
+fn main() {
+    let mut rl = match DefaultEditor::new() {
+        Ok(rl) => rl,
+        Err(err) => {
+            eprintln!("Could not start line editor: {}", err);
+            return;
+        }
+    };
+
+    // A single long-lived environment so bindings persist across lines.
+    let mut env = Env::new();
+
+    loop {
+        match rl.readline("lol> ") {
+            Ok(line) => {
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                let _ = rl.add_history_entry(line);
+                run_line(line, &mut env);
+            }
+            // Ctrl-C cancels the current line, Ctrl-D exits.
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(err) => {
+                eprintln!("Error: {}", err);
+                break;
+            }
+        }
+    }
+}
+
+/// Dispatch a single input line: meta-commands start with `:`, everything else
+/// is parsed and evaluated against the persistent environment.
+fn run_line(line: &str, env: &mut Env) {
+    if let Some(rest) = line.strip_prefix(':') {
+        let (cmd, arg) = match rest.split_once(char::is_whitespace) {
+            Some((cmd, arg)) => (cmd, arg.trim()),
+            None => (rest, ""),
+        };
+        match cmd {
+            "ast" => match parse(&tokenize(arg)) {
+                Ok(ast) => println!("{}", ast),
+                Err(err) => eprintln!("{}", err.render(arg)),
+            },
+            "pe" => match parse(&tokenize(arg)) {
+                Ok(ast) => println!("{}", partially_evaluate(&ast)),
+                Err(err) => eprintln!("{}", err.render(arg)),
+            },
+            "env" => {
+                let mut names: Vec<_> = env.iter().collect();
+                names.sort_by(|a, b| a.0.cmp(b.0));
+                for (name, value) in names {
+                    println!("{} = {}", name, value);
+                }
+            }
+            _ => eprintln!("Unknown command: :{}", cmd),
+        }
+        return;
+    }
+
+    // Parse and evaluate. A parse failure is reported with a caret into the
+    // source; a runtime error carries no span and is printed as is.
+    let ast = match parse(&tokenize(line)) {
+        Ok(ast) => ast,
+        Err(err) => {
+            eprintln!("{}", err.render(line));
+            return;
+        }
+    };
+    match ast.eval(env) {
+        Ok(value) => println!("{}", value),
+        Err(err) => eprintln!("{}", err),
+    }
+}
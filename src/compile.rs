@@ -0,0 +1,481 @@
+//! Lower an [`Expression`] to textual x86-64 assembly.
+//!
+//! Compilation follows the canonical pass sequence:
+//!   1. [`remove_complex_operands`] — make every operator argument an atom.
+//!   2. [`explicate_control`] — flatten nested `let`s into a statement list.
+//!   3. [`select_instructions`] — map each statement to pseudo-x86.
+//!   4. [`assign_homes`] — give each variable a `-8(%rbp)` stack slot.
+//!   5. [`patch_instructions`] — fix instructions with two memory operands.
+//!
+//! The emitted program defines a `main` label and can be assembled by `gcc`.
+
+use crate::parser::{Binding, Expression, LInt, LVar};
+
+/// A failure encountered while lowering an expression to assembly. The backend
+/// only covers the integer core (`Add`/`Subtract`/`Multiply`/`Read`); anything
+/// else is reported rather than aborting the process.
+#[derive(Debug, PartialEq, Clone)]
+pub enum CompileError {
+    Unsupported(&'static str),
+}
+
+impl std::fmt::Display for CompileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CompileError::Unsupported(what) => {
+                write!(f, "error: {} is not supported by the compiler backend", what)
+            }
+        }
+    }
+}
+
+/// An atomic operand: an integer literal or a variable reference.
+#[derive(Debug, Clone)]
+enum Atom {
+    Int(i32),
+    Var(String),
+}
+
+/// A right-hand side in the flattened intermediate language.
+#[derive(Debug, Clone)]
+enum CExpr {
+    Atom(Atom),
+    Read,
+    Add(Atom, Atom),
+    Subtract(Atom, Atom),
+    Multiply(Atom, Atom),
+}
+
+/// A statement in the flattened program: either an assignment or the final
+/// return.
+#[derive(Debug, Clone)]
+enum CStmt {
+    Assign(String, CExpr),
+    Return(CExpr),
+}
+
+/// An x86 operand.
+#[derive(Debug, Clone)]
+enum Arg {
+    Imm(i32),
+    Reg(&'static str),
+    Deref(&'static str, i32),
+    Var(String),
+}
+
+/// A single pseudo-x86 instruction.
+#[derive(Debug, Clone)]
+enum Instr {
+    Movq(Arg, Arg),
+    Addq(Arg, Arg),
+    Subq(Arg, Arg),
+    Imulq(Arg, Arg),
+    Callq(&'static str),
+}
+
+/// Compile an expression to a `gcc`-assemblable x86-64 program.
+pub fn compile(exp: &Expression) -> Result<String, CompileError> {
+    check_supported(exp)?;
+    let mut fresh = 0;
+    let rco = remove_complex_operands(exp, &mut fresh, &RenameEnv::new());
+    let program = explicate_control(&rco);
+    let instrs = select_instructions(&program);
+    let (instrs, homes) = assign_homes(&instrs);
+    let instrs = patch_instructions(&instrs);
+    Ok(emit(&instrs, homes))
+}
+
+/// Reject expressions the backend cannot lower before any pass runs, so an
+/// unsupported construct surfaces as a [`CompileError`] instead of a panic deep
+/// inside the pipeline.
+fn check_supported(exp: &Expression) -> Result<(), CompileError> {
+    match exp {
+        Expression::LInt(LInt::Number(_)) | Expression::LInt(LInt::Read()) => Ok(()),
+        Expression::LInt(LInt::Add(a, b))
+        | Expression::LInt(LInt::Subtract(a, b))
+        | Expression::LInt(LInt::Multiply(a, b)) => {
+            check_supported(a)?;
+            check_supported(b)
+        }
+        Expression::LInt(LInt::Divide(..)) => Err(CompileError::Unsupported("division")),
+        Expression::LVar(LVar::Var(_)) => Ok(()),
+        Expression::LVar(LVar::Let(bindings, body)) => {
+            for binding in bindings {
+                check_supported(&binding.value)?;
+            }
+            check_supported(body)
+        }
+        Expression::LIf(_) => Err(CompileError::Unsupported("booleans and control flow")),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Pass 1: remove-complex-operands
+// ---------------------------------------------------------------------------
+
+/// Maps a user-written `let`-bound name to the fresh, globally unique name it
+/// was alpha-renamed to, so that a shadowing inner `let` never reuses the
+/// outer binding's stack slot.
+type RenameEnv = std::collections::HashMap<String, String>;
+
+/// Flatten the expression so every operator argument is an atom, introducing
+/// fresh `let` temporaries for any nested subexpression. Every user-written
+/// `let` binding is also alpha-renamed to a fresh name under `rename`, so that
+/// a `let` shadowing an outer binding of the same name gets its own stack slot
+/// in `assign_homes` instead of clobbering the outer one.
+fn remove_complex_operands(exp: &Expression, fresh: &mut u32, rename: &RenameEnv) -> Expression {
+    match exp {
+        Expression::LInt(LInt::Number(_)) => exp.clone(),
+        Expression::LVar(LVar::Var(name)) => {
+            Expression::LVar(LVar::Var(rename.get(name).cloned().unwrap_or_else(|| name.clone())))
+        }
+        Expression::LInt(LInt::Read()) => exp.clone(),
+        Expression::LInt(LInt::Add(a, b)) => rco_binop(a, b, fresh, rename, LInt::Add),
+        Expression::LInt(LInt::Subtract(a, b)) => rco_binop(a, b, fresh, rename, LInt::Subtract),
+        Expression::LInt(LInt::Multiply(a, b)) => rco_binop(a, b, fresh, rename, LInt::Multiply),
+        Expression::LVar(LVar::Let(bindings, body)) => {
+            let mut rename = rename.clone();
+            let mut out_bindings = Vec::new();
+            for binding in bindings {
+                let value = remove_complex_operands(&binding.value, fresh, &rename);
+                let name = format!("{}.{}", binding.name, fresh);
+                *fresh += 1;
+                rename.insert(binding.name.clone(), name.clone());
+                out_bindings.push(Binding { name, value });
+            }
+            Expression::LVar(LVar::Let(
+                out_bindings,
+                Box::new(remove_complex_operands(body, fresh, &rename)),
+            ))
+        }
+        other => panic!("{} is not supported by the compiler backend", other),
+    }
+}
+
+/// Reduce both operands of a binary operator to atoms and wrap the result in
+/// the `let` temporaries the reduction required.
+fn rco_binop(
+    a: &Expression,
+    b: &Expression,
+    fresh: &mut u32,
+    rename: &RenameEnv,
+    ctor: fn(Box<Expression>, Box<Expression>) -> LInt,
+) -> Expression {
+    let mut temporaries = Vec::new();
+    let a = rco_atom(a, fresh, rename, &mut temporaries);
+    let b = rco_atom(b, fresh, rename, &mut temporaries);
+    build_lets(temporaries, Expression::LInt(ctor(Box::new(a), Box::new(b))))
+}
+
+/// Return an atomic version of `exp`, pushing any `(name, value)` temporaries
+/// needed to name a complex subexpression.
+fn rco_atom(
+    exp: &Expression,
+    fresh: &mut u32,
+    rename: &RenameEnv,
+    temporaries: &mut Vec<(String, Expression)>,
+) -> Expression {
+    match exp {
+        Expression::LInt(LInt::Number(_)) => exp.clone(),
+        Expression::LVar(LVar::Var(name)) => {
+            Expression::LVar(LVar::Var(rename.get(name).cloned().unwrap_or_else(|| name.clone())))
+        }
+        _ => {
+            let value = remove_complex_operands(exp, fresh, rename);
+            let name = format!("tmp.{}", fresh);
+            *fresh += 1;
+            temporaries.push((name.clone(), value));
+            Expression::LVar(LVar::Var(name))
+        }
+    }
+}
+
+/// Wrap `body` in one single-binding `let` per temporary, outermost first.
+fn build_lets(temporaries: Vec<(String, Expression)>, body: Expression) -> Expression {
+    temporaries.into_iter().rev().fold(body, |body, (name, value)| {
+        Expression::LVar(LVar::Let(
+            vec![Binding { name, value }],
+            Box::new(body),
+        ))
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Pass 2: explicate-control
+// ---------------------------------------------------------------------------
+
+/// Turn the nested `let`s into a flat sequence of assignments ending in a
+/// `return`.
+fn explicate_control(exp: &Expression) -> Vec<CStmt> {
+    let mut stmts = Vec::new();
+    explicate_tail(exp, &mut stmts);
+    stmts
+}
+
+fn explicate_tail(exp: &Expression, stmts: &mut Vec<CStmt>) {
+    match exp {
+        Expression::LVar(LVar::Let(bindings, body)) => {
+            for binding in bindings {
+                explicate_assign(&binding.name, &binding.value, stmts);
+            }
+            explicate_tail(body, stmts);
+        }
+        _ => stmts.push(CStmt::Return(to_cexpr(exp))),
+    }
+}
+
+fn explicate_assign(name: &str, exp: &Expression, stmts: &mut Vec<CStmt>) {
+    match exp {
+        Expression::LVar(LVar::Let(bindings, body)) => {
+            for binding in bindings {
+                explicate_assign(&binding.name, &binding.value, stmts);
+            }
+            explicate_assign(name, body, stmts);
+        }
+        _ => stmts.push(CStmt::Assign(name.to_string(), to_cexpr(exp))),
+    }
+}
+
+/// Convert an already-atomized expression into a flat right-hand side.
+fn to_cexpr(exp: &Expression) -> CExpr {
+    match exp {
+        Expression::LInt(LInt::Number(n)) => CExpr::Atom(Atom::Int(*n)),
+        Expression::LVar(LVar::Var(name)) => CExpr::Atom(Atom::Var(name.clone())),
+        Expression::LInt(LInt::Read()) => CExpr::Read,
+        Expression::LInt(LInt::Add(a, b)) => CExpr::Add(to_atom(a), to_atom(b)),
+        Expression::LInt(LInt::Subtract(a, b)) => CExpr::Subtract(to_atom(a), to_atom(b)),
+        Expression::LInt(LInt::Multiply(a, b)) => CExpr::Multiply(to_atom(a), to_atom(b)),
+        other => panic!("{} is not an atomized expression", other),
+    }
+}
+
+fn to_atom(exp: &Expression) -> Atom {
+    match exp {
+        Expression::LInt(LInt::Number(n)) => Atom::Int(*n),
+        Expression::LVar(LVar::Var(name)) => Atom::Var(name.clone()),
+        other => panic!("{} is not an atom", other),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Pass 3: select-instructions
+// ---------------------------------------------------------------------------
+
+/// Map each statement to pseudo-x86. The return value is left in `%rax`.
+fn select_instructions(program: &[CStmt]) -> Vec<Instr> {
+    let mut instrs = Vec::new();
+    for stmt in program {
+        match stmt {
+            CStmt::Assign(name, expr) => select_assign(Arg::Var(name.clone()), expr, &mut instrs),
+            CStmt::Return(expr) => select_assign(Arg::Reg("rax"), expr, &mut instrs),
+        }
+    }
+    instrs
+}
+
+fn select_assign(dest: Arg, expr: &CExpr, instrs: &mut Vec<Instr>) {
+    match expr {
+        CExpr::Atom(a) => instrs.push(Instr::Movq(atom_arg(a), dest)),
+        CExpr::Read => {
+            instrs.push(Instr::Callq("read_int"));
+            instrs.push(Instr::Movq(Arg::Reg("rax"), dest));
+        }
+        CExpr::Add(a, b) => {
+            instrs.push(Instr::Movq(atom_arg(a), dest.clone()));
+            instrs.push(Instr::Addq(atom_arg(b), dest));
+        }
+        CExpr::Subtract(a, b) => {
+            instrs.push(Instr::Movq(atom_arg(a), dest.clone()));
+            instrs.push(Instr::Subq(atom_arg(b), dest));
+        }
+        CExpr::Multiply(a, b) => {
+            instrs.push(Instr::Movq(atom_arg(a), dest.clone()));
+            instrs.push(Instr::Imulq(atom_arg(b), dest));
+        }
+    }
+}
+
+fn atom_arg(atom: &Atom) -> Arg {
+    match atom {
+        Atom::Int(n) => Arg::Imm(*n),
+        Atom::Var(name) => Arg::Var(name.clone()),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Pass 4: assign-homes
+// ---------------------------------------------------------------------------
+
+/// Allocate each variable a `-8(%rbp)` stack slot and return the rewritten
+/// instructions alongside the number of slots used.
+fn assign_homes(instrs: &[Instr]) -> (Vec<Instr>, usize) {
+    let mut homes: std::collections::HashMap<String, i32> = std::collections::HashMap::new();
+    let out = instrs
+        .iter()
+        .map(|instr| match instr {
+            Instr::Movq(a, b) => Instr::Movq(home(a, &mut homes), home(b, &mut homes)),
+            Instr::Addq(a, b) => Instr::Addq(home(a, &mut homes), home(b, &mut homes)),
+            Instr::Subq(a, b) => Instr::Subq(home(a, &mut homes), home(b, &mut homes)),
+            Instr::Imulq(a, b) => Instr::Imulq(home(a, &mut homes), home(b, &mut homes)),
+            Instr::Callq(label) => Instr::Callq(label),
+        })
+        .collect();
+
+    (out, homes.len())
+}
+
+/// Replace a variable operand with its stack slot, allocating a fresh slot the
+/// first time a variable is seen.
+fn home(arg: &Arg, homes: &mut std::collections::HashMap<String, i32>) -> Arg {
+    match arg {
+        Arg::Var(name) => {
+            let next = -8 * (homes.len() as i32 + 1);
+            let offset = *homes.entry(name.clone()).or_insert(next);
+            Arg::Deref("rbp", offset)
+        }
+        other => other.clone(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Pass 5: patch-instructions
+// ---------------------------------------------------------------------------
+
+/// Fix any instruction whose two operands are both memory references by routing
+/// the source through `%rax`.
+fn patch_instructions(instrs: &[Instr]) -> Vec<Instr> {
+    let mut out = Vec::new();
+    for instr in instrs {
+        match instr {
+            Instr::Movq(a, b) if both_memory(a, b) => patch(Instr::Movq, a, b, &mut out),
+            Instr::Addq(a, b) if both_memory(a, b) => patch(Instr::Addq, a, b, &mut out),
+            Instr::Subq(a, b) if both_memory(a, b) => patch(Instr::Subq, a, b, &mut out),
+            // The two-operand `imul` requires a register destination, so a
+            // stack-slot result is computed in `%rax` and stored back.
+            Instr::Imulq(a, b) if matches!(b, Arg::Deref(..)) => {
+                out.push(Instr::Movq(b.clone(), Arg::Reg("rax")));
+                out.push(Instr::Imulq(a.clone(), Arg::Reg("rax")));
+                out.push(Instr::Movq(Arg::Reg("rax"), b.clone()));
+            }
+            other => out.push(other.clone()),
+        }
+    }
+    out
+}
+
+fn both_memory(a: &Arg, b: &Arg) -> bool {
+    matches!(a, Arg::Deref(..)) && matches!(b, Arg::Deref(..))
+}
+
+fn patch(ctor: fn(Arg, Arg) -> Instr, a: &Arg, b: &Arg, out: &mut Vec<Instr>) {
+    out.push(Instr::Movq(a.clone(), Arg::Reg("rax")));
+    out.push(ctor(Arg::Reg("rax"), b.clone()));
+}
+
+// ---------------------------------------------------------------------------
+// Emission
+// ---------------------------------------------------------------------------
+
+/// Render the instructions into a full program with prologue and epilogue. The
+/// stack frame is rounded up to a 16-byte boundary as the ABI requires.
+fn emit(instrs: &[Instr], homes: usize) -> String {
+    let frame = ((homes * 8) + 15) & !15;
+
+    let mut out = String::new();
+    out.push_str("\t.globl main\n");
+    out.push_str("main:\n");
+    out.push_str("\tpushq %rbp\n");
+    out.push_str("\tmovq %rsp, %rbp\n");
+    if frame > 0 {
+        out.push_str(&format!("\tsubq ${}, %rsp\n", frame));
+    }
+    for instr in instrs {
+        out.push_str(&format!("\t{}\n", instr));
+    }
+    if frame > 0 {
+        out.push_str(&format!("\taddq ${}, %rsp\n", frame));
+    }
+    out.push_str("\tpopq %rbp\n");
+    out.push_str("\tretq\n");
+    out
+}
+
+impl std::fmt::Display for Arg {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Arg::Imm(n) => write!(f, "${}", n),
+            Arg::Reg(r) => write!(f, "%{}", r),
+            Arg::Deref(r, offset) => write!(f, "{}(%{})", offset, r),
+            Arg::Var(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl std::fmt::Display for Instr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Instr::Movq(a, b) => write!(f, "movq {}, {}", a, b),
+            Instr::Addq(a, b) => write!(f, "addq {}, {}", a, b),
+            Instr::Subq(a, b) => write!(f, "subq {}, {}", a, b),
+            Instr::Imulq(a, b) => write!(f, "imulq {}, {}", a, b),
+            Instr::Callq(label) => write!(f, "callq {}", label),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse, tokenize, Value};
+
+    #[test]
+    fn test_compile_emits_main() {
+        let ast = parse(&tokenize("(+ 10 (- 12 (read)))")).unwrap();
+        let asm = compile(&ast).unwrap();
+
+        assert!(asm.contains("\t.globl main\n"));
+        assert!(asm.contains("main:\n"));
+        assert!(asm.contains("callq read_int"));
+        assert!(asm.contains("pushq %rbp"));
+        assert!(asm.trim_end().ends_with("retq"));
+    }
+
+    #[test]
+    fn test_multiply_uses_register_destination() {
+        // A multiply bound in a `let` lands in a stack slot, which the two-operand
+        // `imul` cannot target directly; patching routes it through `%rax`.
+        let ast = parse(&tokenize("(let ((x (* 6 7))) x)")).unwrap();
+        let asm = compile(&ast).unwrap();
+
+        assert!(asm.contains("imulq"));
+        for line in asm.lines().filter(|l| l.contains("imulq")) {
+            assert!(line.contains("%rax"), "imulq must target a register: {}", line);
+        }
+    }
+
+    #[test]
+    fn test_division_is_unsupported() {
+        let ast = parse(&tokenize("(/ 1 0)")).unwrap();
+        assert_eq!(compile(&ast), Err(CompileError::Unsupported("division")));
+    }
+
+    #[test]
+    fn test_shadowed_let_gets_distinct_slots() {
+        // The inner `x` must not clobber the outer `x`'s stack slot before it is
+        // read: `remove_complex_operands` alpha-renames every `let` binding.
+        let ast = parse(&tokenize("(let ((x 1)) (+ x (let ((x 2)) x)))")).unwrap();
+        assert_eq!(ast.evaluate(), Ok(Value::Int(3)));
+
+        let mut fresh = 0;
+        let rco = remove_complex_operands(&ast, &mut fresh, &RenameEnv::new());
+        let program = explicate_control(&rco);
+        let names: Vec<&str> = program
+            .iter()
+            .filter_map(|stmt| match stmt {
+                CStmt::Assign(name, _) => Some(name.as_str()),
+                CStmt::Return(_) => None,
+            })
+            .collect();
+        assert_eq!(names.len(), names.iter().collect::<std::collections::HashSet<_>>().len());
+    }
+}
@@ -1,7 +1,15 @@
 use std::io::Write;
 
-#[derive(Debug, PartialEq)]
-pub enum Token {
+/// A byte range into the original source, used to point error messages at the
+/// offending text.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Span {
+    pub offset: usize,
+    pub len: usize,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum TokenKind {
     Identifier(String),
     Number(i32),
     LParen,
@@ -9,11 +17,20 @@ pub enum Token {
     EndOfFile,
 }
 
+/// A token together with the source span it was lexed from.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum LInt {
     Number(i32),
     Add(Box<Expression>, Box<Expression>),
     Subtract(Box<Expression>, Box<Expression>),
+    Multiply(Box<Expression>, Box<Expression>),
+    Divide(Box<Expression>, Box<Expression>),
     Read(),
 }
 
@@ -30,52 +47,144 @@ pub enum LVar {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+pub enum LIf {
+    Bool(bool),
+    Eq(Box<Expression>, Box<Expression>),
+    Lt(Box<Expression>, Box<Expression>),
+    Le(Box<Expression>, Box<Expression>),
+    Gt(Box<Expression>, Box<Expression>),
+    Ge(Box<Expression>, Box<Expression>),
+    And(Box<Expression>, Box<Expression>),
+    Or(Box<Expression>, Box<Expression>),
+    Not(Box<Expression>),
+    If(Box<Expression>, Box<Expression>, Box<Expression>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+#[allow(clippy::enum_variant_names)]
 pub enum Expression {
     LInt(LInt),
     LVar(LVar),
+    LIf(LIf),
+}
+
+/// A runtime result. Evaluation is no longer always an `i32`: comparisons and
+/// booleans produce `Bool`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Value {
+    Int(i32),
+    Bool(bool),
+}
+
+impl Value {
+    /// Unwrap an `Int`, erroring on a type mismatch.
+    fn as_int(self) -> Result<i32, RuntimeError> {
+        match self {
+            Value::Int(n) => Ok(n),
+            Value::Bool(_) => Err(RuntimeError::TypeMismatch(
+                "expected an integer, found a boolean".to_string(),
+            )),
+        }
+    }
+
+    /// Unwrap a `Bool`, erroring on a type mismatch.
+    fn as_bool(self) -> Result<bool, RuntimeError> {
+        match self {
+            Value::Bool(b) => Ok(b),
+            Value::Int(_) => Err(RuntimeError::TypeMismatch(
+                "expected a boolean, found an integer".to_string(),
+            )),
+        }
+    }
+}
+
+/// A failure encountered while evaluating an expression.
+#[derive(Debug, PartialEq, Clone)]
+pub enum RuntimeError {
+    UnboundVariable(String),
+    ReadParse(String),
+    TypeMismatch(String),
+    DivideByZero,
+}
+
+impl std::fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RuntimeError::UnboundVariable(name) => write!(f, "error: unbound variable `{}`", name),
+            RuntimeError::ReadParse(input) => {
+                write!(f, "error: could not parse `{}` as an integer", input)
+            }
+            RuntimeError::TypeMismatch(message) => write!(f, "error: {}", message),
+            RuntimeError::DivideByZero => write!(f, "error: division by zero"),
+        }
+    }
 }
 
-pub type Env = std::collections::HashMap<String, i32>;
+pub type Env = std::collections::HashMap<String, Value>;
+
+fn is_identifier_char(c: char) -> bool {
+    matches!(c, 'a'..='z' | 'A'..='Z' | '+' | '-' | '*' | '/' | '<' | '>' | '=' | '?')
+}
 
-/// Takes a string and returns a vector of tokens.
+/// Takes a string and returns a vector of tokens, each carrying its source span.
 pub fn tokenize(input: &str) -> Vec<Token> {
     let mut tokens = Vec::new();
-    let mut chars = input.chars().peekable();
+    let mut chars = input.char_indices().peekable();
 
-    while let Some(&c) = chars.peek() {
+    while let Some(&(start, c)) = chars.peek() {
         match c {
             '0'..='9' => {
                 let mut number = String::new();
-                while let Some(&c) = chars.peek() {
-                    match c {
-                        '0'..='9' => {
-                            number.push(c);
-                            chars.next();
-                        }
-                        _ => break,
+                let mut end = start;
+                while let Some(&(i, c)) = chars.peek() {
+                    if c.is_ascii_digit() {
+                        number.push(c);
+                        end = i + c.len_utf8();
+                        chars.next();
+                    } else {
+                        break;
                     }
                 }
-                tokens.push(Token::Number(number.parse().unwrap()));
+                tokens.push(Token {
+                    kind: TokenKind::Number(number.parse().unwrap()),
+                    span: Span {
+                        offset: start,
+                        len: end - start,
+                    },
+                });
             }
-            'a'..='z' | 'A'..='Z' | '+' | '-' | '*' | '/' => {
+            c if is_identifier_char(c) => {
                 let mut identifier = String::new();
-                while let Some(&c) = chars.peek() {
-                    match c {
-                        'a'..='z' | 'A'..='Z' | '+' | '-' | '*' | '/' => {
-                            identifier.push(c);
-                            chars.next();
-                        }
-                        _ => break,
+                let mut end = start;
+                while let Some(&(i, c)) = chars.peek() {
+                    if is_identifier_char(c) {
+                        identifier.push(c);
+                        end = i + c.len_utf8();
+                        chars.next();
+                    } else {
+                        break;
                     }
                 }
-                tokens.push(Token::Identifier(identifier));
+                tokens.push(Token {
+                    kind: TokenKind::Identifier(identifier),
+                    span: Span {
+                        offset: start,
+                        len: end - start,
+                    },
+                });
             }
             '(' => {
-                tokens.push(Token::LParen);
+                tokens.push(Token {
+                    kind: TokenKind::LParen,
+                    span: Span { offset: start, len: 1 },
+                });
                 chars.next();
             }
             ')' => {
-                tokens.push(Token::RParen);
+                tokens.push(Token {
+                    kind: TokenKind::RParen,
+                    span: Span { offset: start, len: 1 },
+                });
                 chars.next();
             }
             _ => {
@@ -84,156 +193,625 @@ pub fn tokenize(input: &str) -> Vec<Token> {
         }
     }
 
-    tokens.push(Token::EndOfFile);
+    tokens.push(Token {
+        kind: TokenKind::EndOfFile,
+        span: Span {
+            offset: input.len(),
+            len: 0,
+        },
+    });
 
     tokens
 }
 
-/// Takes a vector of tokens and returns an AST.
-pub fn parse(tokens: &[Token]) -> Expression {
+type Tokens<'a> = std::iter::Peekable<std::slice::Iter<'a, Token>>;
+
+/// A parse failure: the span of the offending token and an expected-vs-found
+/// message.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseError {
+    pub span: Span,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Render the error against `source`, with a caret underlining the offending
+    /// span in its line.
+    pub fn render(&self, source: &str) -> String {
+        render_span(source, &self.span, &self.message)
+    }
+}
+
+/// Takes a vector of tokens and returns an AST, or the first parse error.
+///
+/// Errors if any tokens remain after the expression, rather than silently
+/// discarding a trailing `garbage nonsense` tail.
+pub fn parse(tokens: &[Token]) -> Result<Expression, ParseError> {
     let mut tokens = tokens.iter().peekable();
-    parse_expression(&mut tokens)
+    let expr = parse_expression(&mut tokens)?;
+    let trailing = next_token(&mut tokens);
+    if trailing.kind != TokenKind::EndOfFile {
+        return Err(ParseError {
+            span: trailing.span.clone(),
+            message: format!("expected end of input, found {}", describe(&trailing.kind)),
+        });
+    }
+    Ok(expr)
+}
+
+/// Parse a single expression in either notation.
+///
+/// Source may be the fully-parenthesized prefix form (`(+ 2 (- 4 2))`) or
+/// conventional infix (`2 + 4 * 3 - x`); both desugar into the same AST.
+fn parse_expression(tokens: &mut Tokens) -> Result<Expression, ParseError> {
+    parse_expression_bp(tokens, 0)
+}
+
+/// Precedence-climbing (Pratt) core. Parses a prefix expression, then keeps
+/// folding in infix operators whose left binding power is at least `min_bp`,
+/// recursing at the operator's right binding power for the right operand.
+fn parse_expression_bp(tokens: &mut Tokens, min_bp: u8) -> Result<Expression, ParseError> {
+    let mut lhs = parse_prefix(tokens)?;
+
+    while let Some(op) = peek_infix_operator(tokens) {
+        let (l_bp, r_bp) = infix_binding_power(&op);
+        if l_bp < min_bp {
+            break;
+        }
+        tokens.next();
+        let rhs = parse_expression_bp(tokens, r_bp)?;
+        lhs = make_infix(&op, lhs, rhs);
+    }
+
+    Ok(lhs)
 }
 
-fn parse_expression(tokens: &mut std::iter::Peekable<std::slice::Iter<'_, Token>>) -> Expression {
-    match tokens.peek() {
-        Some(&Token::Number(_)) => parse_number(tokens),
-        Some(&Token::Identifier(_)) => parse_identifier(tokens),
-        Some(&Token::LParen) => {
-            assert!(tokens.next() == Some(&&Token::LParen));
+/// Prefix parse functions: number literal, identifier, unary `-`, and a
+/// parenthesized group (which also covers the legacy prefix s-expression forms).
+fn parse_prefix(tokens: &mut Tokens) -> Result<Expression, ParseError> {
+    match peek_kind(tokens) {
+        TokenKind::Number(_) => parse_number(tokens),
+        TokenKind::Identifier(id) if id == "-" => {
+            // Unary minus: `- x` desugars to `(- 0 x)`. Without the space, `-x`
+            // lexes as the single identifier `-x`.
+            tokens.next();
+            let operand = parse_expression_bp(tokens, PREFIX_BP)?;
+            Ok(Expression::LInt(LInt::Subtract(
+                Box::new(Expression::LInt(LInt::Number(0))),
+                Box::new(operand),
+            )))
+        }
+        TokenKind::Identifier(_) => parse_identifier(tokens),
+        TokenKind::LParen => parse_parenthesized(tokens),
+        _ => {
+            let tok = next_token(tokens);
+            Err(ParseError {
+                span: tok.span.clone(),
+                message: format!("expected an expression, found {}", describe(&tok.kind)),
+            })
+        }
+    }
+}
 
-            let tok = tokens.next().unwrap();
-            let mut args = Vec::new();
+/// Parse whatever follows a `(`: either a legacy prefix s-expression whose head
+/// is an operator or keyword (`(+ 2 3)`, `(let ...)`, `(read)`), or an ordinary
+/// infix expression wrapped in parentheses for grouping (`(2 + 3)`).
+fn parse_parenthesized(tokens: &mut Tokens) -> Result<Expression, ParseError> {
+    expect(tokens, &TokenKind::LParen)?;
 
-            if *tok == Token::Identifier("let".to_string()) {
-                let mut bindings = Vec::new();
+    let head = match peek_kind(tokens) {
+        TokenKind::Identifier(id) if is_sexpr_head(id) => Some(id.clone()),
+        _ => None,
+    };
 
-                // Skip LParen
-                assert!(tokens.next() == Some(&&Token::LParen));
+    let Some(head) = head else {
+        // Parenthesized infix group: `( expr )`.
+        let inner = parse_expression(tokens)?;
+        expect(tokens, &TokenKind::RParen)?;
+        return Ok(inner);
+    };
 
-                // Parse bindings
-                while tokens.peek() != Some(&&Token::RParen) {
-                    // Skip LParen
-                    assert!(tokens.next() == Some(&&Token::LParen));
+    let head_span = next_token(tokens).span.clone();
 
-                    let name = match tokens.next() {
-                        Some(Token::Identifier(id)) => id,
-                        _ => panic!("Expected identifier"),
-                    };
-                    let value = parse_expression(tokens);
+    if head == "let" {
+        let mut bindings = Vec::new();
 
-                    // Skip RParen
-                    assert!(tokens.next() == Some(&&Token::RParen));
+        expect(tokens, &TokenKind::LParen)?;
+        while peek_kind(tokens) != &TokenKind::RParen {
+            expect(tokens, &TokenKind::LParen)?;
 
-                    bindings.push(Binding {
-                        name: name.to_string(),
-                        value,
-                    });
+            let name_tok = next_token(tokens);
+            let name = match &name_tok.kind {
+                TokenKind::Identifier(id) => id.clone(),
+                other => {
+                    return Err(ParseError {
+                        span: name_tok.span.clone(),
+                        message: format!("expected a binding name, found {}", describe(other)),
+                    })
                 }
+            };
+            let value = parse_expression(tokens)?;
+            expect(tokens, &TokenKind::RParen)?;
 
-                // Skip RParen
-                assert!(tokens.next() == Some(&&Token::RParen));
+            bindings.push(Binding { name, value });
+        }
+        expect(tokens, &TokenKind::RParen)?;
 
-                return Expression::LVar(LVar::Let(bindings, Box::new(parse_expression(tokens))));
-            }
+        let body = parse_expression(tokens)?;
+        expect(tokens, &TokenKind::RParen)?;
+        return Ok(Expression::LVar(LVar::Let(bindings, Box::new(body))));
+    }
 
-            while tokens.peek() != Some(&&Token::RParen) {
-                args.push(parse_expression(tokens));
-            }
-            assert!(tokens.next() == Some(&&Token::RParen));
-            match tok {
-                Token::Identifier(id) => match id.as_str() {
-                    "+" => Expression::LInt(LInt::Add(
-                        Box::new(args[0].clone()),
-                        Box::new(args[1].clone()),
-                    )),
-                    "-" => Expression::LInt(LInt::Subtract(
-                        Box::new(args[0].clone()),
-                        Box::new(args[1].clone()),
-                    )),
-                    "read" => Expression::LInt(LInt::Read()),
-                    _ => panic!("Unexpected token"),
-                },
-                _ => panic!("Unexpected token"),
-            }
+    let mut args = Vec::new();
+    while peek_kind(tokens) != &TokenKind::RParen {
+        args.push(parse_expression(tokens)?);
+    }
+    expect(tokens, &TokenKind::RParen)?;
+
+    build_form(&head, args, &head_span)
+}
+
+/// Build a prefix s-expression form, checking operator arity.
+fn build_form(op: &str, args: Vec<Expression>, span: &Span) -> Result<Expression, ParseError> {
+    let n = args.len();
+    let arity = |expected: usize| -> Result<(), ParseError> {
+        if n == expected {
+            Ok(())
+        } else {
+            Err(ParseError {
+                span: span.clone(),
+                message: format!("`{}` expects {} argument(s), found {}", op, expected, n),
+            })
+        }
+    };
+    let a = |i: usize| Box::new(args[i].clone());
+
+    match op {
+        "+" => {
+            arity(2)?;
+            Ok(Expression::LInt(LInt::Add(a(0), a(1))))
+        }
+        "-" => {
+            arity(2)?;
+            Ok(Expression::LInt(LInt::Subtract(a(0), a(1))))
+        }
+        "*" => {
+            arity(2)?;
+            Ok(Expression::LInt(LInt::Multiply(a(0), a(1))))
+        }
+        "/" => {
+            arity(2)?;
+            Ok(Expression::LInt(LInt::Divide(a(0), a(1))))
         }
-        Some(&Token::RParen) => panic!("Unexpected RParen"),
-        _ => panic!("Unexpected token"),
+        "read" => {
+            arity(0)?;
+            Ok(Expression::LInt(LInt::Read()))
+        }
+        "eq?" => {
+            arity(2)?;
+            Ok(Expression::LIf(LIf::Eq(a(0), a(1))))
+        }
+        "<" => {
+            arity(2)?;
+            Ok(Expression::LIf(LIf::Lt(a(0), a(1))))
+        }
+        "<=" => {
+            arity(2)?;
+            Ok(Expression::LIf(LIf::Le(a(0), a(1))))
+        }
+        ">" => {
+            arity(2)?;
+            Ok(Expression::LIf(LIf::Gt(a(0), a(1))))
+        }
+        ">=" => {
+            arity(2)?;
+            Ok(Expression::LIf(LIf::Ge(a(0), a(1))))
+        }
+        "and" => {
+            arity(2)?;
+            Ok(Expression::LIf(LIf::And(a(0), a(1))))
+        }
+        "or" => {
+            arity(2)?;
+            Ok(Expression::LIf(LIf::Or(a(0), a(1))))
+        }
+        "not" => {
+            arity(1)?;
+            Ok(Expression::LIf(LIf::Not(a(0))))
+        }
+        "if" => {
+            arity(3)?;
+            Ok(Expression::LIf(LIf::If(a(0), a(1), a(2))))
+        }
+        _ => Err(ParseError {
+            span: span.clone(),
+            message: format!("unknown operator `{}`", op),
+        }),
+    }
+}
+
+/// Binding power used when recursing into the operand of a unary prefix `-`;
+/// higher than any infix operator so `-2 + 3` parses as `(-2) + 3`.
+const PREFIX_BP: u8 = 7;
+
+/// Identifiers that head a legacy prefix s-expression rather than an infix group.
+fn is_sexpr_head(id: &str) -> bool {
+    matches!(
+        id,
+        "+" | "-"
+            | "*"
+            | "/"
+            | "read"
+            | "let"
+            | "if"
+            | "and"
+            | "or"
+            | "not"
+            | "eq?"
+            | "<"
+            | "<="
+            | ">"
+            | ">="
+    )
+}
+
+/// The next token as an infix operator string, if it is one.
+fn peek_infix_operator(tokens: &mut Tokens) -> Option<String> {
+    match peek_kind(tokens) {
+        TokenKind::Identifier(id) if matches!(id.as_str(), "+" | "-" | "*" | "/") => {
+            Some(id.clone())
+        }
+        _ => None,
+    }
+}
+
+/// Left and right binding powers for each infix operator. `*`/`/` bind tighter
+/// than `+`/`-`; the right power is one higher than the left to make every
+/// operator left-associative.
+fn infix_binding_power(op: &str) -> (u8, u8) {
+    match op {
+        "+" | "-" => (1, 2),
+        "*" | "/" => (3, 4),
+        _ => panic!("Unexpected operator"),
+    }
+}
+
+fn make_infix(op: &str, lhs: Expression, rhs: Expression) -> Expression {
+    let (lhs, rhs) = (Box::new(lhs), Box::new(rhs));
+    match op {
+        "+" => Expression::LInt(LInt::Add(lhs, rhs)),
+        "-" => Expression::LInt(LInt::Subtract(lhs, rhs)),
+        "*" => Expression::LInt(LInt::Multiply(lhs, rhs)),
+        "/" => Expression::LInt(LInt::Divide(lhs, rhs)),
+        _ => panic!("Unexpected operator"),
     }
 }
 
-fn parse_number(tokens: &mut std::iter::Peekable<std::slice::Iter<'_, Token>>) -> Expression {
-    match tokens.next() {
-        Some(Token::Number(n)) => Expression::LInt(LInt::Number(*n)),
-        _ => panic!("Expected number"),
+fn parse_number(tokens: &mut Tokens) -> Result<Expression, ParseError> {
+    let tok = next_token(tokens);
+    match tok.kind {
+        TokenKind::Number(n) => Ok(Expression::LInt(LInt::Number(n))),
+        ref other => Err(ParseError {
+            span: tok.span.clone(),
+            message: format!("expected a number, found {}", describe(other)),
+        }),
     }
 }
 
-fn parse_identifier(tokens: &mut std::iter::Peekable<std::slice::Iter<'_, Token>>) -> Expression {
-    match tokens.next() {
-        Some(Token::Identifier(id)) => Expression::LVar(LVar::Var(id.to_string())),
-        _ => panic!("Expected identifier"),
+fn parse_identifier(tokens: &mut Tokens) -> Result<Expression, ParseError> {
+    let tok = next_token(tokens);
+    match &tok.kind {
+        TokenKind::Identifier(id) => match id.as_str() {
+            "true" => Ok(Expression::LIf(LIf::Bool(true))),
+            "false" => Ok(Expression::LIf(LIf::Bool(false))),
+            _ => Ok(Expression::LVar(LVar::Var(id.to_string()))),
+        },
+        other => Err(ParseError {
+            span: tok.span.clone(),
+            message: format!("expected an identifier, found {}", describe(other)),
+        }),
     }
 }
 
+/// Peek at the next token's kind. The stream always ends with `EndOfFile`, so
+/// there is always a token to look at.
+fn peek_kind<'a>(tokens: &mut Tokens<'a>) -> &'a TokenKind {
+    &tokens.peek().expect("token stream ends with EndOfFile").kind
+}
+
+/// Consume the next token. The stream always ends with `EndOfFile`.
+fn next_token<'a>(tokens: &mut Tokens<'a>) -> &'a Token {
+    tokens.next().expect("token stream ends with EndOfFile")
+}
+
+/// Consume the next token, erroring unless it matches `kind`.
+fn expect(tokens: &mut Tokens, kind: &TokenKind) -> Result<(), ParseError> {
+    let tok = next_token(tokens);
+    if &tok.kind == kind {
+        Ok(())
+    } else {
+        Err(ParseError {
+            span: tok.span.clone(),
+            message: format!("expected {}, found {}", describe(kind), describe(&tok.kind)),
+        })
+    }
+}
+
+/// A human-readable description of a token kind for error messages.
+fn describe(kind: &TokenKind) -> String {
+    match kind {
+        TokenKind::Identifier(id) => format!("`{}`", id),
+        TokenKind::Number(n) => format!("`{}`", n),
+        TokenKind::LParen => "`(`".to_string(),
+        TokenKind::RParen => "`)`".to_string(),
+        TokenKind::EndOfFile => "end of input".to_string(),
+    }
+}
+
+/// Render `message` against the source line containing `span`, with a caret
+/// underlining the span.
+pub fn render_span(source: &str, span: &Span, message: &str) -> String {
+    let line_start = source[..span.offset].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[span.offset..]
+        .find('\n')
+        .map_or(source.len(), |i| span.offset + i);
+    let line = &source[line_start..line_end];
+    let column = span.offset - line_start;
+    let caret = "^".repeat(span.len.max(1));
+    format!(
+        "error: {}\n{}\n{}{}",
+        message,
+        line,
+        " ".repeat(column),
+        caret
+    )
+}
+
 pub trait Eval {
-    fn eval(&self, env: &mut Env) -> i32;
+    fn eval(&self, env: &mut Env) -> Result<Value, RuntimeError>;
 }
 
 impl Eval for LInt {
-    fn eval(&self, env: &mut Env) -> i32 {
+    fn eval(&self, env: &mut Env) -> Result<Value, RuntimeError> {
         match self {
-            LInt::Number(n) => *n,
-            LInt::Add(a, b) => a.eval(env) + b.eval(env),
-            LInt::Subtract(a, b) => a.eval(env) - b.eval(env),
+            LInt::Number(n) => Ok(Value::Int(*n)),
+            LInt::Add(a, b) => Ok(Value::Int(a.eval(env)?.as_int()? + b.eval(env)?.as_int()?)),
+            LInt::Subtract(a, b) => Ok(Value::Int(a.eval(env)?.as_int()? - b.eval(env)?.as_int()?)),
+            LInt::Multiply(a, b) => Ok(Value::Int(a.eval(env)?.as_int()? * b.eval(env)?.as_int()?)),
+            LInt::Divide(a, b) => {
+                let dividend = a.eval(env)?.as_int()?;
+                let divisor = b.eval(env)?.as_int()?;
+                if divisor == 0 {
+                    Err(RuntimeError::DivideByZero)
+                } else {
+                    Ok(Value::Int(dividend / divisor))
+                }
+            }
             LInt::Read() => {
                 let mut input = String::new();
                 print!("> ");
                 // flush
                 std::io::stdout().flush().unwrap();
                 std::io::stdin().read_line(&mut input).unwrap();
-                input.trim().parse().unwrap()
+                input
+                    .trim()
+                    .parse()
+                    .map(Value::Int)
+                    .map_err(|_| RuntimeError::ReadParse(input.trim().to_string()))
             }
         }
     }
 }
 
 impl Eval for LVar {
-    fn eval(&self, env: &mut Env) -> i32 {
+    fn eval(&self, env: &mut Env) -> Result<Value, RuntimeError> {
         match self {
             LVar::Let(bindings, body) => {
                 for binding in bindings {
-                    let value = binding.value.eval(env);
+                    let value = binding.value.eval(env)?;
                     env.insert(binding.name.clone(), value);
                 }
                 body.eval(env)
             }
-            LVar::Var(name) => env.get(name).unwrap().clone(),
+            LVar::Var(name) => env
+                .get(name)
+                .copied()
+                .ok_or_else(|| RuntimeError::UnboundVariable(name.clone())),
+        }
+    }
+}
+
+impl Eval for LIf {
+    fn eval(&self, env: &mut Env) -> Result<Value, RuntimeError> {
+        match self {
+            LIf::Bool(b) => Ok(Value::Bool(*b)),
+            LIf::Eq(a, b) => Ok(Value::Bool(a.eval(env)? == b.eval(env)?)),
+            LIf::Lt(a, b) => Ok(Value::Bool(a.eval(env)?.as_int()? < b.eval(env)?.as_int()?)),
+            LIf::Le(a, b) => Ok(Value::Bool(a.eval(env)?.as_int()? <= b.eval(env)?.as_int()?)),
+            LIf::Gt(a, b) => Ok(Value::Bool(a.eval(env)?.as_int()? > b.eval(env)?.as_int()?)),
+            LIf::Ge(a, b) => Ok(Value::Bool(a.eval(env)?.as_int()? >= b.eval(env)?.as_int()?)),
+            // `and`/`or` short-circuit: the right operand is only evaluated when
+            // the left does not already decide the result.
+            LIf::And(a, b) => {
+                if a.eval(env)?.as_bool()? {
+                    Ok(Value::Bool(b.eval(env)?.as_bool()?))
+                } else {
+                    Ok(Value::Bool(false))
+                }
+            }
+            LIf::Or(a, b) => {
+                if a.eval(env)?.as_bool()? {
+                    Ok(Value::Bool(true))
+                } else {
+                    Ok(Value::Bool(b.eval(env)?.as_bool()?))
+                }
+            }
+            LIf::Not(a) => Ok(Value::Bool(!a.eval(env)?.as_bool()?)),
+            // Only the taken branch is evaluated.
+            LIf::If(cond, then, otherwise) => {
+                if cond.eval(env)?.as_bool()? {
+                    then.eval(env)
+                } else {
+                    otherwise.eval(env)
+                }
+            }
         }
     }
 }
 
 impl Expression {
-    pub fn evaluate(&self) -> i32 {
+    pub fn evaluate(&self) -> Result<Value, RuntimeError> {
         let mut env = Env::new();
         self.eval(&mut env)
     }
 }
 
 impl Eval for Expression {
-    fn eval(&self, env: &mut Env) -> i32 {
+    fn eval(&self, env: &mut Env) -> Result<Value, RuntimeError> {
         match self {
             Expression::LInt(lint) => lint.eval(env),
             Expression::LVar(lvar) => lvar.eval(env),
+            Expression::LIf(lif) => lif.eval(env),
         }
     }
 }
 
+/// A compile-time environment mapping names to the constant they were bound to.
+type ConstEnv = std::collections::HashMap<String, i32>;
+
+/// Partially evaluate an expression, folding constants across `let` bindings.
+///
+/// A binding whose value folds to a constant is recorded in the environment and
+/// dropped from the residual program; a binding that still depends on `(read)`
+/// stays a `let` and its uses remain `Var` references. Bindings are processed in
+/// order so later ones see earlier constants, and shadowing is respected.
+pub fn partially_evaluate(exp: &Expression) -> Expression {
+    fold_constants(exp, &ConstEnv::new())
+}
+
+fn fold_constants(exp: &Expression, env: &ConstEnv) -> Expression {
+    match exp {
+        Expression::LInt(lint) => match lint {
+            LInt::Number(n) => Expression::LInt(LInt::Number(*n)),
+            LInt::Add(a, b) => fold_binop(a, b, env, LInt::Add, |x, y| Some(x + y)),
+            LInt::Subtract(a, b) => fold_binop(a, b, env, LInt::Subtract, |x, y| Some(x - y)),
+            LInt::Multiply(a, b) => fold_binop(a, b, env, LInt::Multiply, |x, y| Some(x * y)),
+            // Division by a constant zero is left as a residual `Divide` rather
+            // than folded, so partial evaluation never divides by zero.
+            LInt::Divide(a, b) => {
+                fold_binop(a, b, env, LInt::Divide, |x, y| (y != 0).then(|| x / y))
+            }
+            LInt::Read() => Expression::LInt(LInt::Read()),
+        },
+        Expression::LVar(lvar) => match lvar {
+            LVar::Var(name) => match env.get(name) {
+                Some(n) => Expression::LInt(LInt::Number(*n)),
+                None => Expression::LVar(LVar::Var(name.clone())),
+            },
+            LVar::Let(bindings, body) => {
+                let mut env = env.clone();
+                let mut residual = Vec::new();
+                for binding in bindings {
+                    match fold_constants(&binding.value, &env) {
+                        Expression::LInt(LInt::Number(n)) => {
+                            env.insert(binding.name.clone(), n);
+                        }
+                        value => {
+                            // The binding is not a constant, so an inner binding
+                            // of the same name must shadow any outer constant.
+                            env.remove(&binding.name);
+                            residual.push(Binding {
+                                name: binding.name.clone(),
+                                value,
+                            });
+                        }
+                    }
+                }
+                let body = fold_constants(body, &env);
+                if residual.is_empty() {
+                    body
+                } else {
+                    Expression::LVar(LVar::Let(residual, Box::new(body)))
+                }
+            }
+        },
+        // The control-flow operator itself is not folded away, but constants
+        // still propagate into its condition and branches.
+        Expression::LIf(lif) => Expression::LIf(fold_lif(lif, env)),
+    }
+}
+
+/// Fold a binary operator: if both operands reduce to constants and `op` yields
+/// a value, evaluate it; otherwise keep the residual expression over the reduced
+/// operands. `op` returns `None` to decline the fold (e.g. division by zero).
+fn fold_binop(
+    a: &Expression,
+    b: &Expression,
+    env: &ConstEnv,
+    ctor: fn(Box<Expression>, Box<Expression>) -> LInt,
+    op: fn(i32, i32) -> Option<i32>,
+) -> Expression {
+    let a = fold_constants(a, env);
+    let b = fold_constants(b, env);
+    match (&a, &b) {
+        (Expression::LInt(LInt::Number(x)), Expression::LInt(LInt::Number(y))) => match op(*x, *y) {
+            Some(n) => Expression::LInt(LInt::Number(n)),
+            None => Expression::LInt(ctor(Box::new(a), Box::new(b))),
+        },
+        _ => Expression::LInt(ctor(Box::new(a), Box::new(b))),
+    }
+}
+
+/// Propagate constants into the operands of a control-flow form, leaving the
+/// form itself intact.
+fn fold_lif(lif: &LIf, env: &ConstEnv) -> LIf {
+    let fold = |e: &Expression| Box::new(fold_constants(e, env));
+    match lif {
+        LIf::Bool(b) => LIf::Bool(*b),
+        LIf::Eq(a, b) => LIf::Eq(fold(a), fold(b)),
+        LIf::Lt(a, b) => LIf::Lt(fold(a), fold(b)),
+        LIf::Le(a, b) => LIf::Le(fold(a), fold(b)),
+        LIf::Gt(a, b) => LIf::Gt(fold(a), fold(b)),
+        LIf::Ge(a, b) => LIf::Ge(fold(a), fold(b)),
+        LIf::And(a, b) => LIf::And(fold(a), fold(b)),
+        LIf::Or(a, b) => LIf::Or(fold(a), fold(b)),
+        LIf::Not(a) => LIf::Not(fold(a)),
+        LIf::If(cond, then, otherwise) => LIf::If(fold(cond), fold(then), fold(otherwise)),
+    }
+}
+
 impl std::fmt::Display for Expression {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             Expression::LInt(lint) => write!(f, "{}", lint),
             Expression::LVar(lvar) => write!(f, "{}", lvar),
+            Expression::LIf(lif) => write!(f, "{}", lif),
+        }
+    }
+}
+
+impl std::fmt::Display for LIf {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            LIf::Bool(b) => write!(f, "{}", b),
+            LIf::Eq(a, b) => write!(f, "(eq? {} {})", a, b),
+            LIf::Lt(a, b) => write!(f, "(< {} {})", a, b),
+            LIf::Le(a, b) => write!(f, "(<= {} {})", a, b),
+            LIf::Gt(a, b) => write!(f, "(> {} {})", a, b),
+            LIf::Ge(a, b) => write!(f, "(>= {} {})", a, b),
+            LIf::And(a, b) => write!(f, "(and {} {})", a, b),
+            LIf::Or(a, b) => write!(f, "(or {} {})", a, b),
+            LIf::Not(a) => write!(f, "(not {})", a),
+            LIf::If(cond, then, otherwise) => write!(f, "(if {} {} {})", cond, then, otherwise),
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Value::Int(n) => write!(f, "{}", n),
+            Value::Bool(b) => write!(f, "{}", b),
         }
     }
 }
@@ -244,6 +822,8 @@ impl std::fmt::Display for LInt {
             LInt::Number(n) => write!(f, "{}", n),
             LInt::Add(a, b) => write!(f, "(+ {} {})", a, b),
             LInt::Subtract(a, b) => write!(f, "(- {} {})", a, b),
+            LInt::Multiply(a, b) => write!(f, "(* {} {})", a, b),
+            LInt::Divide(a, b) => write!(f, "(/ {} {})", a, b),
             LInt::Read() => write!(f, "(read)"),
         }
     }
@@ -268,61 +848,71 @@ impl std::fmt::Display for LVar {
 mod tests {
     use super::*;
 
+    /// Collect just the token kinds, ignoring spans, for concise assertions.
+    fn kinds(input: &str) -> Vec<TokenKind> {
+        tokenize(input).into_iter().map(|t| t.kind).collect()
+    }
+
     #[test]
     fn test_tokenize() {
         let input = "(add 2 (subtract 4 2))";
         let expected = vec![
-            Token::LParen,
-            Token::Identifier("add".to_string()),
-            Token::Number(2),
-            Token::LParen,
-            Token::Identifier("subtract".to_string()),
-            Token::Number(4),
-            Token::Number(2),
-            Token::RParen,
-            Token::RParen,
-            Token::EndOfFile,
+            TokenKind::LParen,
+            TokenKind::Identifier("add".to_string()),
+            TokenKind::Number(2),
+            TokenKind::LParen,
+            TokenKind::Identifier("subtract".to_string()),
+            TokenKind::Number(4),
+            TokenKind::Number(2),
+            TokenKind::RParen,
+            TokenKind::RParen,
+            TokenKind::EndOfFile,
         ];
 
-        assert_eq!(tokenize(input), expected);
+        assert_eq!(kinds(input), expected);
 
         let input = "(let ((x 2) (y 3)) (let ((z (+ x y))) (+ z 1)))";
         let expected = vec![
-            Token::LParen,
-            Token::Identifier("let".to_string()),
-            Token::LParen,
-            Token::LParen,
-            Token::Identifier("x".to_string()),
-            Token::Number(2),
-            Token::RParen,
-            Token::LParen,
-            Token::Identifier("y".to_string()),
-            Token::Number(3),
-            Token::RParen,
-            Token::RParen,
-            Token::LParen,
-            Token::Identifier("let".to_string()),
-            Token::LParen,
-            Token::LParen,
-            Token::Identifier("z".to_string()),
-            Token::LParen,
-            Token::Identifier("+".to_string()),
-            Token::Identifier("x".to_string()),
-            Token::Identifier("y".to_string()),
-            Token::RParen,
-            Token::RParen,
-            Token::RParen,
-            Token::LParen,
-            Token::Identifier("+".to_string()),
-            Token::Identifier("z".to_string()),
-            Token::Number(1),
-            Token::RParen,
-            Token::RParen,
-            Token::RParen,
-            Token::EndOfFile,
+            TokenKind::LParen,
+            TokenKind::Identifier("let".to_string()),
+            TokenKind::LParen,
+            TokenKind::LParen,
+            TokenKind::Identifier("x".to_string()),
+            TokenKind::Number(2),
+            TokenKind::RParen,
+            TokenKind::LParen,
+            TokenKind::Identifier("y".to_string()),
+            TokenKind::Number(3),
+            TokenKind::RParen,
+            TokenKind::RParen,
+            TokenKind::LParen,
+            TokenKind::Identifier("let".to_string()),
+            TokenKind::LParen,
+            TokenKind::LParen,
+            TokenKind::Identifier("z".to_string()),
+            TokenKind::LParen,
+            TokenKind::Identifier("+".to_string()),
+            TokenKind::Identifier("x".to_string()),
+            TokenKind::Identifier("y".to_string()),
+            TokenKind::RParen,
+            TokenKind::RParen,
+            TokenKind::RParen,
+            TokenKind::LParen,
+            TokenKind::Identifier("+".to_string()),
+            TokenKind::Identifier("z".to_string()),
+            TokenKind::Number(1),
+            TokenKind::RParen,
+            TokenKind::RParen,
+            TokenKind::RParen,
+            TokenKind::EndOfFile,
         ];
 
-        assert_eq!(tokenize(input), expected);
+        assert_eq!(kinds(input), expected);
+
+        // Spans point back at the source text.
+        let tokens = tokenize("(+ 12 3)");
+        assert_eq!(tokens[2].kind, TokenKind::Number(12));
+        assert_eq!(tokens[2].span, Span { offset: 3, len: 2 });
     }
 
     #[test]
@@ -337,7 +927,7 @@ mod tests {
             ))),
         ));
 
-        assert_eq!(parse(&tokens), expected);
+        assert_eq!(parse(&tokens), Ok(expected));
 
         // let input = "(- 100 (+ 3 4 (read)))";
         // let tokens = tokenize(input);
@@ -359,12 +949,106 @@ mod tests {
         // assert_eq!(parse(&tokens), expected);
     }
 
+    #[test]
+    fn test_parse_infix() {
+        // Infix precedence: `*` binds tighter than `+`/`-`, all left-associative.
+        let input = "2 + 4 * 3 - 1";
+        let tokens = tokenize(input);
+        let expected = Expression::LInt(LInt::Subtract(
+            Box::new(Expression::LInt(LInt::Add(
+                Box::new(Expression::LInt(LInt::Number(2))),
+                Box::new(Expression::LInt(LInt::Multiply(
+                    Box::new(Expression::LInt(LInt::Number(4))),
+                    Box::new(Expression::LInt(LInt::Number(3))),
+                ))),
+            ))),
+            Box::new(Expression::LInt(LInt::Number(1))),
+        ));
+
+        assert_eq!(parse(&tokens), Ok(expected));
+
+        // Both notations coexist and agree on the result.
+        assert_eq!(
+            parse(&tokenize("2 + 4 * 3 - 1")).unwrap().evaluate(),
+            Ok(Value::Int(13))
+        );
+        assert_eq!(
+            parse(&tokenize("(+ 2 (- 4 2))")).unwrap().evaluate(),
+            Ok(Value::Int(4))
+        );
+    }
+
+    #[test]
+    fn test_if() {
+        let input = "(if (< 1 2) (+ 10 5) 0)";
+        let tokens = tokenize(input);
+        let ast = parse(&tokens).unwrap();
+
+        assert_eq!(ast.evaluate(), Ok(Value::Int(15)));
+
+        // Comparisons and logical operators yield booleans.
+        assert_eq!(
+            parse(&tokenize("(and true false)")).unwrap().evaluate(),
+            Ok(Value::Bool(false))
+        );
+        assert_eq!(
+            parse(&tokenize("(not (eq? 3 4))")).unwrap().evaluate(),
+            Ok(Value::Bool(true))
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        // An unbound variable is a runtime error rather than a panic.
+        let ast = parse(&tokenize("x")).unwrap();
+        assert_eq!(
+            ast.evaluate(),
+            Err(RuntimeError::UnboundVariable("x".to_string()))
+        );
+
+        // A missing closing paren reports the offending span.
+        let err = parse(&tokenize("(+ 1 2")).unwrap_err();
+        assert!(err.message.contains("expected"));
+
+        // Trailing tokens after a complete expression are an error, not a
+        // silently-discarded tail.
+        let err = parse(&tokenize("1 < 2")).unwrap_err();
+        assert!(err.message.contains("end of input"));
+
+        let err = parse(&tokenize("(+ 1 2) garbage nonsense")).unwrap_err();
+        assert!(err.message.contains("end of input"));
+    }
+
+    #[test]
+    fn test_pe_propagates_into_if() {
+        // Dropping the constant `let` must substitute `x` into the `if`, not
+        // leave it dangling. The residual evaluates without an unbound variable.
+        let ast = parse(&tokenize("(let ((x 5)) (if (eq? x 5) 1 0))")).unwrap();
+        let residual = partially_evaluate(&ast);
+        assert_eq!(residual.evaluate(), Ok(Value::Int(1)));
+        assert_eq!(residual.to_string(), "(if (eq? 5 5) 1 0)");
+    }
+
+    #[test]
+    fn test_pe_leaves_divide_by_zero_residual() {
+        // Folding must not divide by a constant zero; the `Divide` survives.
+        let ast = parse(&tokenize("(/ 1 0)")).unwrap();
+        assert_eq!(partially_evaluate(&ast).to_string(), "(/ 1 0)");
+    }
+
+    #[test]
+    fn test_divide_by_zero() {
+        // Dividing by zero is a runtime error, not a process-aborting panic.
+        let ast = parse(&tokenize("(/ 1 0)")).unwrap();
+        assert_eq!(ast.evaluate(), Err(RuntimeError::DivideByZero));
+    }
+
     #[test]
     fn test_eval() {
         let input = "(+ 2 (- 4 2))";
         let tokens = tokenize(input);
-        let ast = parse(&tokens);
-        let expected = 4;
+        let ast = parse(&tokens).unwrap();
+        let expected = Ok(Value::Int(4));
 
         assert_eq!(ast.evaluate(), expected);
 